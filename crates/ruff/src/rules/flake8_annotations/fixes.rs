@@ -0,0 +1,61 @@
+use anyhow::{bail, Result};
+
+use ruff_diagnostics::Edit;
+use ruff_python_ast::{Arg, Expr, Ranged, Stmt};
+use ruff_python_whitespace::{SimpleTokenizer, TokenKind};
+use ruff_text_size::TextRange;
+
+use crate::source_code::Locator;
+
+use super::helpers::match_function_def;
+
+/// Adds a return type annotation to `stmt`'s signature, just before its trailing colon, e.g.
+/// `def foo():` becomes `def foo() -> int:`. Scans forward for the first top-level `:` (i.e. one
+/// that isn't nested inside the argument list, like the one in an `x: int` parameter) so
+/// the insertion point is always the colon that actually opens the function body.
+pub(crate) fn add_return_annotation(locator: &Locator, stmt: &Stmt, annotation: &str) -> Result<Edit> {
+    let (.., body, _) = match_function_def(stmt);
+    let Some(first_stmt) = body.first() else {
+        bail!("function body is unexpectedly empty");
+    };
+
+    let mut depth = 0u32;
+    let mut colon = None;
+    for token in SimpleTokenizer::new(
+        locator.contents(),
+        TextRange::new(stmt.start(), first_stmt.start()),
+    ) {
+        match token.kind() {
+            TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+            TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => depth -= 1,
+            TokenKind::Colon if depth == 0 => {
+                colon = Some(token.start());
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(colon) = colon else {
+        bail!("could not find the signature's trailing colon");
+    };
+
+    Ok(Edit::insertion(format!(" -> {annotation}"), colon))
+}
+
+/// Adds a type annotation to a function argument that's missing one, e.g. `x=0` becomes
+/// `x: int = 0`. The only caller, `definition`, only ever infers a type (via
+/// `infer_argument_type`) from a literal default, so `default` is always present; the gap between the argument's name and its default
+/// is replaced wholesale so the `=` picks up the PEP 8-mandated surrounding spaces (E252) rather
+/// than being left butted up against the annotation as `x: int=0`.
+pub(crate) fn add_argument_annotation(
+    _locator: &Locator,
+    arg: &Arg,
+    annotation: &str,
+    default: &Expr,
+) -> Result<Edit> {
+    Ok(Edit::range_replacement(
+        format!(": {annotation} = "),
+        TextRange::new(arg.end(), default.start()),
+    ))
+}
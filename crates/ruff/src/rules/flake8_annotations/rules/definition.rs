@@ -1,22 +1,28 @@
 use rustpython_parser::ast::{self, ArgWithDefault, Constant, Expr, Ranged, Stmt};
 
-use ruff_diagnostics::{AlwaysAutofixableViolation, Diagnostic, Fix, Violation};
+use ruff_diagnostics::{
+    AlwaysAutofixableViolation, AutofixKind, Diagnostic, Edit, Fix, Violation,
+};
 use ruff_macros::{derive_message_formats, violation};
 use ruff_python_ast::cast;
 use ruff_python_ast::helpers::ReturnStatementVisitor;
 use ruff_python_ast::identifier::Identifier;
 use ruff_python_ast::statement_visitor::StatementVisitor;
 use ruff_python_ast::typing::parse_type_annotation;
+use ruff_python_ast::visitor::{self, Visitor};
 use ruff_python_semantic::analyze::visibility;
 use ruff_python_semantic::{Definition, Member, MemberKind};
 use ruff_python_stdlib::typing::simple_magic_return_type;
+use ruff_text_size::TextSize;
 
 use crate::checkers::ast::Checker;
+use crate::importer::ImportRequest;
 use crate::registry::{AsRule, Rule};
 use crate::rules::ruff::typing::type_hint_resolves_to_any;
 
 use super::super::fixes;
 use super::super::helpers::match_function_def;
+use super::super::settings::AnnotationStyle;
 
 /// ## What it does
 /// Checks that function arguments have type annotations.
@@ -43,11 +49,17 @@ pub struct MissingTypeFunctionArgument {
 }
 
 impl Violation for MissingTypeFunctionArgument {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         let MissingTypeFunctionArgument { name } = self;
         format!("Missing type annotation for function argument `{name}`")
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Add argument type annotation".to_string())
+    }
 }
 
 /// ## What it does
@@ -209,17 +221,33 @@ impl Violation for MissingTypeCls {
 /// def add(a: int, b: int) -> int:
 ///     return a + b
 /// ```
+///
+/// When this rule's autofix infers a typing-module construct such as `Optional[int]`, the
+/// `annotation-style` setting controls how it's spelled: `smart` (default) emits the bare name,
+/// importing it if it isn't already in scope, while `fully-qualified` emits e.g.
+/// `typing.Optional[int]` so no import is added:
+///
+/// ```toml
+/// [tool.ruff.flake8-annotations]
+/// annotation-style = "fully-qualified"
+/// ```
 #[violation]
 pub struct MissingReturnTypeUndocumentedPublicFunction {
     name: String,
 }
 
 impl Violation for MissingReturnTypeUndocumentedPublicFunction {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         let MissingReturnTypeUndocumentedPublicFunction { name } = self;
         format!("Missing return type annotation for public function `{name}`")
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Add return type annotation".to_string())
+    }
 }
 
 /// ## What it does
@@ -247,11 +275,17 @@ pub struct MissingReturnTypePrivateFunction {
 }
 
 impl Violation for MissingReturnTypePrivateFunction {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         let MissingReturnTypePrivateFunction { name } = self;
         format!("Missing return type annotation for private function `{name}`")
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Add return type annotation".to_string())
+    }
 }
 
 /// ## What it does
@@ -332,11 +366,17 @@ pub struct MissingReturnTypeStaticMethod {
 }
 
 impl Violation for MissingReturnTypeStaticMethod {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         let MissingReturnTypeStaticMethod { name } = self;
         format!("Missing return type annotation for staticmethod `{name}`")
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Add return type annotation".to_string())
+    }
 }
 
 /// ## What it does
@@ -368,11 +408,17 @@ pub struct MissingReturnTypeClassMethod {
 }
 
 impl Violation for MissingReturnTypeClassMethod {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         let MissingReturnTypeClassMethod { name } = self;
         format!("Missing return type annotation for classmethod `{name}`")
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Add return type annotation".to_string())
+    }
 }
 
 /// ## What it does
@@ -387,6 +433,15 @@ impl Violation for MissingReturnTypeClassMethod {
 /// It's better to be explicit about the type of an expression, and to use
 /// `Any` as an "escape hatch" only when it is really needed.
 ///
+/// By default, this rule only flags `Any` when it's the annotation's entire type. Set
+/// `allow-nested-any = false` to also flag `Any` nested inside a composite annotation, like
+/// `list[Any]` or `int | Any`:
+///
+/// ```toml
+/// [tool.ruff.flake8-annotations]
+/// allow-nested-any = false
+/// ```
+///
 /// ## Example
 /// ```python
 /// def foo(x: Any):
@@ -432,6 +487,179 @@ fn is_none_returning(body: &[Stmt]) -> bool {
     true
 }
 
+/// Returns `true` if `body` contains a `yield` or `yield from` expression, ignoring any nested
+/// function, lambda, or class scope (a `yield` there belongs to that scope's return type, not
+/// this one's).
+fn contains_yield(body: &[Stmt]) -> bool {
+    struct YieldVisitor {
+        found: bool,
+    }
+
+    impl<'a> Visitor<'a> for YieldVisitor {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            match stmt {
+                Stmt::FunctionDef(_) | Stmt::AsyncFunctionDef(_) | Stmt::ClassDef(_) => {
+                    // A `yield` in a nested scope doesn't make this function a generator.
+                }
+                _ => visitor::walk_stmt(self, stmt),
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            match expr {
+                Expr::Yield(_) | Expr::YieldFrom(_) => self.found = true,
+                Expr::Lambda(_) => {}
+                _ => visitor::walk_expr(self, expr),
+            }
+        }
+    }
+
+    let mut visitor = YieldVisitor { found: false };
+    for stmt in body {
+        visitor.visit_stmt(stmt);
+    }
+    visitor.found
+}
+
+/// Maps a constant value to a builtin type name, if it's one recognizable without a type checker.
+/// Returns `None` for anything else (e.g. tuples, ellipsis), in which case the caller should give
+/// up on inferring a type.
+fn constant_type_name(value: &Constant) -> Option<&'static str> {
+    Some(match value {
+        Constant::None => "None",
+        Constant::Bool(_) => "bool",
+        Constant::Int(_) => "int",
+        Constant::Float(_) => "float",
+        Constant::Complex { .. } => "complex",
+        Constant::Str(_) => "str",
+        Constant::Bytes(_) => "bytes",
+        _ => return None,
+    })
+}
+
+/// Maps a `return <value>` expression to a builtin type name, if `value` is a literal constant
+/// recognizable without a type checker. Returns `None` for anything else (names, calls, binary
+/// expressions, etc.), in which case the caller should give up on inferring a return type.
+fn literal_return_type(value: &Expr) -> Option<&'static str> {
+    let Expr::Constant(ast::ExprConstant { value, .. }) = value else {
+        return None;
+    };
+    constant_type_name(value)
+}
+
+/// Maps a parameter's default value to a builtin type name, if it's a literal constant
+/// recognizable without a type checker. Returns `None` for `None` specifically (the correct
+/// annotation would be `Optional[...]`, which can't be determined from the default alone) and for
+/// anything else that isn't a literal constant.
+fn infer_argument_type(default: &Expr) -> Option<&'static str> {
+    let Expr::Constant(ast::ExprConstant { value, .. }) = default else {
+        return None;
+    };
+    match value {
+        Constant::None => None,
+        _ => constant_type_name(value),
+    }
+}
+
+/// Renders a typing-module symbol (`Optional` or `Union`) as it should appear in an inferred
+/// annotation, per the `annotation-style` setting. In [`AnnotationStyle::FullyQualified`] mode,
+/// the symbol is qualified with its `typing.` prefix and no import is necessary. In
+/// [`AnnotationStyle::Smart`] mode (the default), the bare name is used instead; if `name` isn't
+/// already in scope per `checker.semantic()`, an edit importing it is appended to `extra_edits`.
+/// Returns `None` if the importer can't bring `name` into scope.
+fn render_typing_symbol(
+    checker: &Checker,
+    name: &'static str,
+    at: TextSize,
+    extra_edits: &mut Vec<Edit>,
+) -> Option<String> {
+    match checker.settings.flake8_annotations.annotation_style {
+        AnnotationStyle::FullyQualified => Some(format!("typing.{name}")),
+        AnnotationStyle::Smart => {
+            let (edit, binding) = checker
+                .importer()
+                .get_or_import_symbol(
+                    &ImportRequest::import_from("typing", name),
+                    at,
+                    checker.semantic(),
+                )
+                .ok()?;
+            extra_edits.push(edit);
+            Some(binding)
+        }
+    }
+}
+
+/// Infers a return-type annotation for `body` from its `return` statements, the way a type
+/// checker's return-coercion would: every returned literal maps to its builtin type name, the
+/// distinct types are joined into a union if there's more than one, rendered as `X | Y` on
+/// `target_version_minor >= 10` and a `Union[X, Y]` (or `Optional[X]`, if the only other member
+/// is `None`) otherwise, spelled per the `annotation-style` setting (see [`render_typing_symbol`]).
+/// A function with no explicit `return <value>` (bare `return`, implicit fall-through, or no
+/// `return` at all) infers as `None`.
+///
+/// Returns `None` (bail, no suggested fix) if `body` contains a `yield`, or if any returned value
+/// isn't a literal constant [`literal_return_type`] can map to a type, or if a needed `Optional`
+/// or `Union` can't be imported (see [`render_typing_symbol`]). Otherwise, returns the
+/// rendered annotation alongside any import edits needed to bring it into scope.
+fn infer_return_type(checker: &Checker, body: &[Stmt], at: TextSize) -> Option<(String, Vec<Edit>)> {
+    if contains_yield(body) {
+        return None;
+    }
+
+    let mut visitor = ReturnStatementVisitor::default();
+    visitor.visit_body(body);
+
+    let mut types: Vec<&'static str> = Vec::new();
+    for stmt in &visitor.returns {
+        let type_name = match stmt.value.as_deref() {
+            Some(value) => literal_return_type(value)?,
+            None => "None",
+        };
+        if !types.contains(&type_name) {
+            types.push(type_name);
+        }
+    }
+
+    if types.is_empty() {
+        types.push("None");
+    }
+
+    let mut extra_edits = Vec::new();
+    let mut unresolved = false;
+    let annotation = render_return_annotation(&types, checker.settings.target_version.minor(), |name| {
+        render_typing_symbol(checker, name, at, &mut extra_edits).unwrap_or_else(|| {
+            unresolved = true;
+            name.to_string()
+        })
+    });
+
+    if unresolved {
+        return None;
+    }
+
+    Some((annotation, extra_edits))
+}
+
+/// The pure decision core of [`infer_return_type`]: given the distinct inferred return types,
+/// picks between a single type, a PEP 604 union, `Optional[...]`, or `Union[...]`, calling
+/// `render_symbol` to spell out `Optional`/`Union` per the `annotation-style` setting. Split out
+/// from [`infer_return_type`] so the branching can be unit-tested without a [`Checker`].
+fn render_return_annotation(
+    types: &[&'static str],
+    minor: u32,
+    mut render_symbol: impl FnMut(&'static str) -> String,
+) -> String {
+    match types {
+        [single] => (*single).to_string(),
+        // Checked ahead of the `Optional` arm below: on 3.10+, a two-member `X | None` union is
+        // rendered as `X | None` via PEP 604, not `Optional[X]`.
+        _ if minor >= 10 => types.join(" | "),
+        [other, "None"] | ["None", other] => format!("{}[{other}]", render_symbol("Optional")),
+        _ => format!("{}[{}]", render_symbol("Union"), types.join(", ")),
+    }
+}
+
 /// ANN401
 fn check_dynamically_typed<F>(
     checker: &Checker,
@@ -439,40 +667,71 @@ fn check_dynamically_typed<F>(
     func: F,
     diagnostics: &mut Vec<Diagnostic>,
 ) where
-    F: FnOnce() -> String,
+    F: Fn() -> String,
 {
+    // Quoted annotations: re-parse the string and recurse into the result, so that the rest of
+    // this function doesn't need to care whether it's looking at a quoted or unquoted annotation.
     if let Expr::Constant(ast::ExprConstant {
         range,
         value: Constant::Str(string),
         ..
     }) = annotation
     {
-        // Quoted annotations
         if let Ok((parsed_annotation, _)) = parse_type_annotation(string, *range, checker.locator) {
-            if type_hint_resolves_to_any(
-                &parsed_annotation,
-                checker.semantic(),
-                checker.locator,
-                checker.settings.target_version.minor(),
-            ) {
-                diagnostics.push(Diagnostic::new(
-                    AnyType { name: func() },
-                    annotation.range(),
-                ));
+            check_dynamically_typed(checker, &parsed_annotation, func, diagnostics);
+        }
+        return;
+    }
+
+    if type_hint_resolves_to_any(
+        annotation,
+        checker.semantic(),
+        checker.locator,
+        checker.settings.target_version.minor(),
+    ) {
+        diagnostics.push(Diagnostic::new(AnyType { name: func() }, annotation.range()));
+        return;
+    }
+
+    if checker.settings.flake8_annotations.allow_nested_any {
+        return;
+    }
+
+    // Descend into composite annotations (`list[Any]`, `Optional[Any]`, `int | Any`, ...) looking
+    // for a nested `Any`, reporting it at its own range rather than the whole annotation's.
+    match annotation {
+        Expr::Subscript(ast::ExprSubscript { value, slice, .. }) => {
+            // `Literal[...]`'s slice holds values, not types, so there's nothing to check inside.
+            let is_literal = checker
+                .semantic()
+                .resolve_call_path(value)
+                .is_some_and(|call_path| matches!(call_path.as_slice(), ["typing", "Literal"]));
+            if is_literal {
+                return;
+            }
+
+            // Recurse through `&dyn Fn` so the generic instantiates once more, not without end.
+            let func: &dyn Fn() -> String = &func;
+            match slice.as_ref() {
+                Expr::Tuple(ast::ExprTuple { elts, .. }) => {
+                    for elt in elts {
+                        check_dynamically_typed(checker, elt, func, diagnostics);
+                    }
+                }
+                slice => check_dynamically_typed(checker, slice, func, diagnostics),
             }
         }
-    } else {
-        if type_hint_resolves_to_any(
-            annotation,
-            checker.semantic(),
-            checker.locator,
-            checker.settings.target_version.minor(),
-        ) {
-            diagnostics.push(Diagnostic::new(
-                AnyType { name: func() },
-                annotation.range(),
-            ));
+        Expr::BinOp(ast::ExprBinOp {
+            left,
+            op: ast::Operator::BitOr,
+            right,
+            ..
+        }) => {
+            let func: &dyn Fn() -> String = &func;
+            check_dynamically_typed(checker, left, func, diagnostics);
+            check_dynamically_typed(checker, right, func, diagnostics);
         }
+        _ => {}
     }
 }
 
@@ -510,7 +769,7 @@ pub(crate) fn definition(
     // ANN001, ANN401
     for ArgWithDefault {
         def,
-        default: _,
+        default,
         range: _,
     } in arguments
         .posonlyargs
@@ -541,12 +800,29 @@ pub(crate) fn definition(
                 && checker.settings.dummy_variable_rgx.is_match(&def.arg))
             {
                 if checker.enabled(Rule::MissingTypeFunctionArgument) {
-                    diagnostics.push(Diagnostic::new(
+                    let mut diagnostic = Diagnostic::new(
                         MissingTypeFunctionArgument {
                             name: def.arg.to_string(),
                         },
                         def.range(),
-                    ));
+                    );
+                    if checker.patch(diagnostic.kind.rule()) {
+                        if let Some((default, arg_type)) = default
+                            .as_deref()
+                            .and_then(|default| Some((default, infer_argument_type(default)?)))
+                        {
+                            diagnostic.try_set_fix(|| {
+                                fixes::add_argument_annotation(
+                                    checker.locator,
+                                    def,
+                                    arg_type,
+                                    default,
+                                )
+                                .map(Fix::suggested)
+                            });
+                        }
+                    }
+                    diagnostics.push(diagnostic);
                 }
             }
         }
@@ -659,23 +935,45 @@ pub(crate) fn definition(
     ) {
         if is_method && visibility::is_classmethod(cast::decorator_list(stmt), checker.semantic()) {
             if checker.enabled(Rule::MissingReturnTypeClassMethod) {
-                diagnostics.push(Diagnostic::new(
+                let mut diagnostic = Diagnostic::new(
                     MissingReturnTypeClassMethod {
                         name: name.to_string(),
                     },
                     stmt.identifier(),
-                ));
+                );
+                if checker.patch(diagnostic.kind.rule()) {
+                    if let Some((return_type, extra_edits)) =
+                        infer_return_type(checker, body, stmt.start())
+                    {
+                        diagnostic.try_set_fix(|| {
+                            fixes::add_return_annotation(checker.locator, stmt, &return_type)
+                                .map(|edit| Fix::suggested_edits(edit, extra_edits))
+                        });
+                    }
+                }
+                diagnostics.push(diagnostic);
             }
         } else if is_method
             && visibility::is_staticmethod(cast::decorator_list(stmt), checker.semantic())
         {
             if checker.enabled(Rule::MissingReturnTypeStaticMethod) {
-                diagnostics.push(Diagnostic::new(
+                let mut diagnostic = Diagnostic::new(
                     MissingReturnTypeStaticMethod {
                         name: name.to_string(),
                     },
                     stmt.identifier(),
-                ));
+                );
+                if checker.patch(diagnostic.kind.rule()) {
+                    if let Some((return_type, extra_edits)) =
+                        infer_return_type(checker, body, stmt.start())
+                    {
+                        diagnostic.try_set_fix(|| {
+                            fixes::add_return_annotation(checker.locator, stmt, &return_type)
+                                .map(|edit| Fix::suggested_edits(edit, extra_edits))
+                        });
+                    }
+                }
+                diagnostics.push(diagnostic);
             }
         } else if is_method && visibility::is_init(name) {
             // Allow omission of return annotation in `__init__` functions, as long as at
@@ -719,22 +1017,52 @@ pub(crate) fn definition(
             match visibility {
                 visibility::Visibility::Public => {
                     if checker.enabled(Rule::MissingReturnTypeUndocumentedPublicFunction) {
-                        diagnostics.push(Diagnostic::new(
+                        let mut diagnostic = Diagnostic::new(
                             MissingReturnTypeUndocumentedPublicFunction {
                                 name: name.to_string(),
                             },
                             stmt.identifier(),
-                        ));
+                        );
+                        if checker.patch(diagnostic.kind.rule()) {
+                            if let Some((return_type, extra_edits)) =
+                                infer_return_type(checker, body, stmt.start())
+                            {
+                                diagnostic.try_set_fix(|| {
+                                    fixes::add_return_annotation(
+                                        checker.locator,
+                                        stmt,
+                                        &return_type,
+                                    )
+                                    .map(|edit| Fix::suggested_edits(edit, extra_edits))
+                                });
+                            }
+                        }
+                        diagnostics.push(diagnostic);
                     }
                 }
                 visibility::Visibility::Private => {
                     if checker.enabled(Rule::MissingReturnTypePrivateFunction) {
-                        diagnostics.push(Diagnostic::new(
+                        let mut diagnostic = Diagnostic::new(
                             MissingReturnTypePrivateFunction {
                                 name: name.to_string(),
                             },
                             stmt.identifier(),
-                        ));
+                        );
+                        if checker.patch(diagnostic.kind.rule()) {
+                            if let Some((return_type, extra_edits)) =
+                                infer_return_type(checker, body, stmt.start())
+                            {
+                                diagnostic.try_set_fix(|| {
+                                    fixes::add_return_annotation(
+                                        checker.locator,
+                                        stmt,
+                                        &return_type,
+                                    )
+                                    .map(|edit| Fix::suggested_edits(edit, extra_edits))
+                                });
+                            }
+                        }
+                        diagnostics.push(diagnostic);
                     }
                 }
             }
@@ -750,3 +1078,104 @@ pub(crate) fn definition(
         diagnostics
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rustpython_parser::ast::{self, Constant, Expr, ExprContext};
+    use ruff_text_size::TextRange;
+
+    use super::{infer_argument_type, literal_return_type, render_return_annotation};
+
+    fn constant(value: Constant) -> Expr {
+        ast::ExprConstant {
+            value,
+            kind: None,
+            range: TextRange::default(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn infer_argument_type_from_literal_default() {
+        assert_eq!(infer_argument_type(&constant(Constant::Bool(true))), Some("bool"));
+        assert_eq!(
+            infer_argument_type(&constant(Constant::Str("x".to_string()))),
+            Some("str")
+        );
+        assert_eq!(infer_argument_type(&constant(Constant::Float(1.0))), Some("float"));
+    }
+
+    #[test]
+    fn infer_argument_type_none_default_is_not_inferred() {
+        // `None` alone can't tell us the real type (it'd need `Optional[...]`), so it's
+        // deliberately excluded rather than inferred as `"None"`.
+        assert_eq!(infer_argument_type(&constant(Constant::None)), None);
+    }
+
+    #[test]
+    fn infer_argument_type_non_constant_default() {
+        let expr: Expr = ast::ExprName {
+            id: "CONST".into(),
+            ctx: ExprContext::Load,
+            range: TextRange::default(),
+        }
+        .into();
+
+        assert_eq!(infer_argument_type(&expr), None);
+    }
+
+    #[test]
+    fn literal_return_type_maps_constants() {
+        assert_eq!(literal_return_type(&constant(Constant::None)), Some("None"));
+        assert_eq!(literal_return_type(&constant(Constant::Bytes(vec![]))), Some("bytes"));
+    }
+
+    #[test]
+    fn literal_return_type_non_constant_value() {
+        let expr: Expr = ast::ExprName {
+            id: "x".into(),
+            ctx: ExprContext::Load,
+            range: TextRange::default(),
+        }
+        .into();
+
+        assert_eq!(literal_return_type(&expr), None);
+    }
+
+    #[test]
+    fn render_return_annotation_single_type() {
+        assert_eq!(
+            render_return_annotation(&["int"], 9, |_| unreachable!("no symbol needed")),
+            "int"
+        );
+    }
+
+    #[test]
+    fn render_return_annotation_optional_below_3_10() {
+        assert_eq!(
+            render_return_annotation(&["int", "None"], 9, |name| name.to_string()),
+            "Optional[int]"
+        );
+        assert_eq!(
+            render_return_annotation(&["None", "int"], 9, |name| name.to_string()),
+            "Optional[int]"
+        );
+    }
+
+    #[test]
+    fn render_return_annotation_pep604_on_3_10_plus() {
+        // On 3.10+, even a two-member `X | None` union renders as `X | None`, not `Optional[X]`.
+        assert_eq!(
+            render_return_annotation(&["int", "None"], 10, |_| unreachable!("no symbol needed")),
+            "int | None"
+        );
+    }
+
+    #[test]
+    fn render_return_annotation_union_below_3_10() {
+        assert_eq!(
+            render_return_annotation(&["int", "str", "bytes"], 9, |name| name.to_string()),
+            "Union[int, str, bytes]"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Settings for the `flake8-annotations` plugin.
+
+use ruff_macros::CacheKey;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, CacheKey)]
+pub struct Settings {
+    pub mypy_init_return: bool,
+    pub suppress_dummy_args: bool,
+    pub suppress_none_returning: bool,
+    pub allow_star_arg_any: bool,
+    pub ignore_fully_untyped: bool,
+    /// See [`Options::allow_nested_any`].
+    pub allow_nested_any: bool,
+    /// See [`Options::annotation_style`].
+    pub annotation_style: AnnotationStyle,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            mypy_init_return: false,
+            suppress_dummy_args: false,
+            suppress_none_returning: false,
+            allow_star_arg_any: false,
+            ignore_fully_untyped: false,
+            allow_nested_any: true,
+            annotation_style: AnnotationStyle::default(),
+        }
+    }
+}
+
+/// The style used to render a typing-module construct (e.g. `Optional`, `Union`) that an
+/// autofix in this rule group inserts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, CacheKey, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnnotationStyle {
+    /// Render the bare name (e.g. `Optional[int]`), importing it via the existing importer if
+    /// it isn't already in scope.
+    #[default]
+    Smart,
+    /// Render the symbol qualified with its `typing.` prefix (e.g. `typing.Optional[int]`), so
+    /// no import needs to be added.
+    FullyQualified,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Options {
+    /// Whether to allow the omission of a return type hint for `__init__` if at least one
+    /// argument is annotated, to match `mypy`'s behavior.
+    pub mypy_init_return: Option<bool>,
+
+    /// Whether to suppress `ANN000`-level violations for arguments matching the
+    /// "dummy variable" pattern (like `_`).
+    pub suppress_dummy_args: Option<bool>,
+
+    /// Whether to suppress `ANN200`-level violations for functions that meet either of the
+    /// following criteria:
+    ///
+    /// - Contain no `return` statement.
+    /// - Explicit `return` statement(s) all return `None` (explicitly or implicitly).
+    pub suppress_none_returning: Option<bool>,
+
+    /// Whether to suppress `ANN401` for dynamically typed `*args` and `**kwargs` arguments.
+    pub allow_star_arg_any: Option<bool>,
+
+    /// Whether to suppress `ANN*` rules for any declaration that hasn't been typed at all.
+    /// This makes it easier to gradually increase annotation coverage in large codebases.
+    pub ignore_fully_untyped: Option<bool>,
+
+    /// Whether to allow `Any` nested inside of a composite annotation, e.g. `list[Any]` or
+    /// `int | Any`. Defaults to `true`; set to `false` to also flag nested `Any` under
+    /// `ANN401`.
+    pub allow_nested_any: Option<bool>,
+
+    /// The style to use when an autofix in this rule group needs to insert a typing-module
+    /// construct like `Optional` or `Union`: `"smart"` (default) renders the bare name and
+    /// imports it if it isn't already in scope, while `"fully-qualified"` renders it as e.g.
+    /// `typing.Optional` so no import is required.
+    pub annotation_style: Option<AnnotationStyle>,
+}
+
+impl From<Options> for Settings {
+    fn from(options: Options) -> Self {
+        Self {
+            mypy_init_return: options.mypy_init_return.unwrap_or_default(),
+            suppress_dummy_args: options.suppress_dummy_args.unwrap_or_default(),
+            suppress_none_returning: options.suppress_none_returning.unwrap_or_default(),
+            allow_star_arg_any: options.allow_star_arg_any.unwrap_or_default(),
+            ignore_fully_untyped: options.ignore_fully_untyped.unwrap_or_default(),
+            allow_nested_any: options.allow_nested_any.unwrap_or(true),
+            annotation_style: options.annotation_style.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Settings> for Options {
+    fn from(settings: Settings) -> Self {
+        Self {
+            mypy_init_return: Some(settings.mypy_init_return),
+            suppress_dummy_args: Some(settings.suppress_dummy_args),
+            suppress_none_returning: Some(settings.suppress_none_returning),
+            allow_star_arg_any: Some(settings.allow_star_arg_any),
+            ignore_fully_untyped: Some(settings.ignore_fully_untyped),
+            allow_nested_any: Some(settings.allow_nested_any),
+            annotation_style: Some(settings.annotation_style),
+        }
+    }
+}
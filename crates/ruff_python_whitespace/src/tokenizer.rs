@@ -110,10 +110,188 @@ fn is_non_ascii_identifier_start(c: char) -> bool {
     is_xid_start(c)
 }
 
+/// Returns `true` if `prefix` is a valid (case-insensitive) Python string or byte-string prefix,
+/// e.g. `r`, `F`, `Rb`. The empty string is a valid prefix (an un-prefixed string).
+fn is_string_prefix(prefix: &str) -> bool {
+    matches!(
+        prefix.to_ascii_lowercase().as_str(),
+        "" | "r" | "u" | "b" | "f" | "rb" | "br" | "rf" | "fr"
+    )
+}
+
+/// If `source` starts with a (possibly prefixed) string quote, e.g. `"`, `r"`, `"""`, `fr'''`,
+/// returns the length of the prefix (0 if un-prefixed) and the number of quote characters (1 or 3).
+fn match_string_start(source: &str) -> Option<(usize, usize)> {
+    let bytes = source.as_bytes();
+
+    let prefix_len = (0..=2)
+        .rev()
+        .find(|&len| matches!(bytes.get(len), Some(b'\'' | b'"')) && is_string_prefix(&source[..len]))?;
+
+    let quote = bytes[prefix_len];
+    let quote_len = if bytes.get(prefix_len + 1) == Some(&quote) && bytes.get(prefix_len + 2) == Some(&quote)
+    {
+        3
+    } else {
+        1
+    };
+
+    Some((prefix_len, quote_len))
+}
+
+/// Computes the [`Position`] of `offset` in `source` from scratch, by scanning `source[..offset]`.
+/// Intended to be called once, to seed [`SimpleTokenizer::with_positions`]; per-token positions
+/// are derived incrementally from there instead.
+fn position_at(source: &str, offset: TextSize) -> Position {
+    let mut line = 0;
+    let mut column = 0;
+
+    let mut chars = source[..offset.to_usize()].chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                line += 1;
+                column = 0;
+            }
+            '\r' => {
+                line += 1;
+                column = 0;
+                chars.next_if_eq(&'\n');
+            }
+            _ => column += 1,
+        }
+    }
+
+    Position {
+        offset,
+        line,
+        column,
+    }
+}
+
+/// Returns the number of newlines (`\n`, `\r`, or `\r\n`, each counting once) in `text`.
+fn count_newlines(text: &str) -> u32 {
+    let mut count = 0;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => count += 1,
+            '\r' => {
+                count += 1;
+                chars.next_if_eq(&'\n');
+            }
+            _ => {}
+        }
+    }
+
+    count
+}
+
+/// Returns the column at `offset`, found by scanning back through `source` for the preceding
+/// newline (or the start of `source`, if there is none).
+fn column_before(source: &str, offset: TextSize) -> u32 {
+    let before = &source[..offset.to_usize()];
+    match before.rfind(|c| matches!(c, '\n' | '\r')) {
+        Some(index) => before[index + 1..].chars().count() as u32,
+        None => before.chars().count() as u32,
+    }
+}
+
+/// Returns the [`Position`] reached after advancing `position` by `text`, which must immediately
+/// follow `position` in the source.
+fn advance_position(position: Position, text: &str) -> Position {
+    let mut line = position.line;
+    let mut column = position.column;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                line += 1;
+                column = 0;
+            }
+            '\r' => {
+                line += 1;
+                column = 0;
+                chars.next_if_eq(&'\n');
+            }
+            _ => column += 1,
+        }
+    }
+
+    Position {
+        offset: position.offset + text.text_len(),
+        line,
+        column,
+    }
+}
+
+/// Returns `offset` shifted by `delta` bytes, e.g. to account for an edit earlier in the file.
+fn shift_offset(offset: TextSize, delta: i64) -> TextSize {
+    if delta >= 0 {
+        offset + TextSize::new(delta as u32)
+    } else {
+        offset - TextSize::new((-delta) as u32)
+    }
+}
+
+/// Returns `token` shifted by `delta` bytes. Only valid when the edit that `delta` accounts for
+/// didn't add or remove any newlines between `token` and the edit, since only the offset (not the
+/// line/column) of any [`Position`]s is adjusted.
+fn shift_token(token: &Token, delta: i64) -> Token {
+    let shift_position = |position: Position| Position {
+        offset: shift_offset(position.offset, delta),
+        ..position
+    };
+
+    Token {
+        kind: token.kind,
+        range: TextRange::new(
+            shift_offset(token.range.start(), delta),
+            shift_offset(token.range.end(), delta),
+        ),
+        start_position: token.start_position.map(shift_position),
+        end_position: token.end_position.map(shift_position),
+    }
+}
+
+/// If `source` ends with a string prefix (e.g. `r`, `Rb`), returns its length; `0` otherwise.
+fn match_string_prefix_end(source: &str) -> usize {
+    (1..=2)
+        .rev()
+        .find(|&len| {
+            // `is_char_boundary` must be checked before slicing at `source.len() - len`: a
+            // multi-byte character sitting directly before the string's opening quote (with no
+            // ASCII separator) would otherwise land this index mid-codepoint and panic, the way
+            // the forward twin `match_string_start` avoids by checking `bytes.get(len)` before
+            // ever slicing.
+            source.len() >= len
+                && source.is_char_boundary(source.len() - len)
+                && source[source.len() - len..]
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic())
+                && is_string_prefix(&source[source.len() - len..])
+        })
+        .unwrap_or(0)
+}
+
+/// A position within the source text, tracked incrementally as the tokenizer advances, the way
+/// character-by-character lexers like `just`'s do. `line` and `column` are both zero-indexed;
+/// `column` counts characters (not bytes) since the start of the line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Position {
+    pub offset: TextSize,
+    pub line: u32,
+    pub column: u32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Token {
     pub kind: TokenKind,
     pub range: TextRange,
+    start_position: Option<Position>,
+    end_position: Option<Position>,
 }
 
 impl Token {
@@ -133,6 +311,18 @@ impl Token {
     pub const fn end(&self) -> TextSize {
         self.range.end()
     }
+
+    /// Returns the line and column at which this token starts, or `None` if the tokenizer that
+    /// produced it wasn't created with [`SimpleTokenizer::with_positions`].
+    pub const fn start_position(&self) -> Option<Position> {
+        self.start_position
+    }
+
+    /// Returns the line and column at which this token ends, or `None` if the tokenizer that
+    /// produced it wasn't created with [`SimpleTokenizer::with_positions`].
+    pub const fn end_position(&self) -> Option<Position> {
+        self.end_position
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -185,32 +375,236 @@ pub enum TokenKind {
     /// `.`.
     Dot,
 
+    /// `;`
+    Semi,
+
+    /// `~`
+    Tilde,
+
+    /// `=`
+    Equal,
+
+    /// `==`
+    EqEqual,
+
+    /// `!=`
+    NotEqual,
+
+    /// `<`
+    Less,
+
+    /// `<=`
+    LessEqual,
+
+    /// `<<`
+    LeftShift,
+
+    /// `<<=`
+    LeftShiftEqual,
+
+    /// `>`
+    Greater,
+
+    /// `>=`
+    GreaterEqual,
+
+    /// `>>`
+    RightShift,
+
+    /// `>>=`
+    RightShiftEqual,
+
+    /// `+`
+    Plus,
+
+    /// `+=`
+    PlusEqual,
+
+    /// `-`
+    Minus,
+
+    /// `-=`
+    MinusEqual,
+
+    /// `->`
+    Rarrow,
+
+    /// `%`
+    Percent,
+
+    /// `%=`
+    PercentEqual,
+
+    /// `&`
+    Amper,
+
+    /// `&=`
+    AmperEqual,
+
+    /// `|`
+    Vbar,
+
+    /// `|=`
+    VbarEqual,
+
+    /// `^`
+    Circumflex,
+
+    /// `^=`
+    CircumflexEqual,
+
+    /// `@`
+    At,
+
+    /// `@=`
+    AtEqual,
+
+    /// `:=`
+    ColonEqual,
+
+    /// `**`
+    DoubleStar,
+
+    /// `**=`
+    DoubleStarEqual,
+
+    /// `*=`
+    StarEqual,
+
+    /// `//`
+    DoubleSlash,
+
+    /// `//=`
+    DoubleSlashEqual,
+
+    /// `/=`
+    SlashEqual,
+
+    /// A string (or byte-string) literal, including any prefix (e.g. `r`, `b`, `f`) and its
+    /// quotes. Spans the entire literal, even if unterminated or multiline; f-strings are
+    /// lexed as a single opaque token rather than into their replacement fields.
+    String,
+
+    /// `False`
+    False,
+
+    /// `None`
+    None,
+
+    /// `True`
+    True,
+
+    /// `and`
+    And,
+
+    /// `as`
+    As,
+
+    /// `assert`
+    Assert,
+
+    /// `async`
+    Async,
+
+    /// `await`
+    Await,
+
+    /// `break`
+    Break,
+
+    /// `class`
+    Class,
+
+    /// `continue`
+    Continue,
+
+    /// `def`
+    Def,
+
+    /// `del`
+    Del,
+
+    /// `elif`
+    Elif,
+
     /// `else`
     Else,
 
+    /// `except`
+    Except,
+
+    /// `finally`
+    Finally,
+
+    /// `for`
+    For,
+
+    /// `from`
+    From,
+
+    /// `global`
+    Global,
+
     /// `if`
     If,
 
+    /// `import`
+    Import,
+
     /// `in`
     In,
 
-    /// `as`
-    As,
+    /// `is`
+    Is,
 
-    /// `match`
-    Match,
+    /// `lambda`
+    Lambda,
+
+    /// `nonlocal`
+    Nonlocal,
+
+    /// `not`
+    Not,
+
+    /// `or`
+    Or,
+
+    /// `pass`
+    Pass,
+
+    /// `raise`
+    Raise,
+
+    /// `return`
+    Return,
+
+    /// `try`
+    Try,
+
+    /// `while`
+    While,
 
     /// `with`
     With,
 
-    /// `async`
-    Async,
+    /// `yield`
+    Yield,
+
+    /// `match`, a soft keyword: an identifier everywhere except where it starts a match statement.
+    Match,
+
+    /// `case`, a soft keyword: an identifier everywhere except inside a match statement.
+    Case,
+
+    /// `type`, a soft keyword: an identifier everywhere except where it starts a type alias.
+    Type,
+
+    /// A run of identifier-continuation characters that doesn't start with an identifier-start
+    /// character, e.g. `555`.
+    Number,
 
     /// Any other non trivia token.
     Other,
-
-    /// Returned for each character after [`TokenKind::Other`] has been returned once.
-    Bogus,
 }
 
 impl TokenKind {
@@ -227,10 +621,89 @@ impl TokenKind {
             '/' => TokenKind::Slash,
             '*' => TokenKind::Star,
             '.' => TokenKind::Dot,
+            ';' => TokenKind::Semi,
+            '~' => TokenKind::Tilde,
+            '=' => TokenKind::Equal,
+            '<' => TokenKind::Less,
+            '>' => TokenKind::Greater,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '%' => TokenKind::Percent,
+            '&' => TokenKind::Amper,
+            '|' => TokenKind::Vbar,
+            '^' => TokenKind::Circumflex,
+            '@' => TokenKind::At,
             _ => TokenKind::Other,
         }
     }
 
+    /// Determines the operator kind for the characters `(c1, c2, c3)`, given in left-to-right
+    /// source order, where `c1` is the character lexing started from and `c2`/`c3` are the
+    /// characters that follow it, if any (not yet consumed). Returns the kind together with how
+    /// many of `c2`/`c3` (0, 1, or 2) are part of the operator, which the caller must consume.
+    const fn match_operator(c1: char, c2: Option<char>, c3: Option<char>) -> (TokenKind, u32) {
+        match (c1, c2, c3) {
+            ('*', Some('*'), Some('=')) => (TokenKind::DoubleStarEqual, 2),
+            ('*', Some('*'), _) => (TokenKind::DoubleStar, 1),
+            ('*', Some('='), _) => (TokenKind::StarEqual, 1),
+            ('/', Some('/'), Some('=')) => (TokenKind::DoubleSlashEqual, 2),
+            ('/', Some('/'), _) => (TokenKind::DoubleSlash, 1),
+            ('/', Some('='), _) => (TokenKind::SlashEqual, 1),
+            ('<', Some('<'), Some('=')) => (TokenKind::LeftShiftEqual, 2),
+            ('<', Some('<'), _) => (TokenKind::LeftShift, 1),
+            ('<', Some('='), _) => (TokenKind::LessEqual, 1),
+            ('>', Some('>'), Some('=')) => (TokenKind::RightShiftEqual, 2),
+            ('>', Some('>'), _) => (TokenKind::RightShift, 1),
+            ('>', Some('='), _) => (TokenKind::GreaterEqual, 1),
+            ('=', Some('='), _) => (TokenKind::EqEqual, 1),
+            ('!', Some('='), _) => (TokenKind::NotEqual, 1),
+            (':', Some('='), _) => (TokenKind::ColonEqual, 1),
+            ('-', Some('>'), _) => (TokenKind::Rarrow, 1),
+            ('-', Some('='), _) => (TokenKind::MinusEqual, 1),
+            ('+', Some('='), _) => (TokenKind::PlusEqual, 1),
+            ('%', Some('='), _) => (TokenKind::PercentEqual, 1),
+            ('&', Some('='), _) => (TokenKind::AmperEqual, 1),
+            ('|', Some('='), _) => (TokenKind::VbarEqual, 1),
+            ('^', Some('='), _) => (TokenKind::CircumflexEqual, 1),
+            ('@', Some('='), _) => (TokenKind::AtEqual, 1),
+            _ => (TokenKind::from_non_trivia_char(c1), 0),
+        }
+    }
+
+    /// Mirrors [`TokenKind::match_operator`], but scans right-to-left: `last` is the character
+    /// already consumed going backwards (the rightmost character of the operator), and
+    /// `prev`/`prev2` are the one and two characters immediately preceding it in the source, if
+    /// any. Returns the kind together with how many of `prev`/`prev2` (0, 1, or 2) are part of the
+    /// operator, which the caller must skip back over.
+    const fn match_operator_back(last: char, prev: Option<char>, prev2: Option<char>) -> (TokenKind, u32) {
+        match (prev2, prev, last) {
+            (Some('*'), Some('*'), '=') => (TokenKind::DoubleStarEqual, 2),
+            (_, Some('*'), '*') => (TokenKind::DoubleStar, 1),
+            (_, Some('*'), '=') => (TokenKind::StarEqual, 1),
+            (Some('/'), Some('/'), '=') => (TokenKind::DoubleSlashEqual, 2),
+            (_, Some('/'), '/') => (TokenKind::DoubleSlash, 1),
+            (_, Some('/'), '=') => (TokenKind::SlashEqual, 1),
+            (Some('<'), Some('<'), '=') => (TokenKind::LeftShiftEqual, 2),
+            (_, Some('<'), '<') => (TokenKind::LeftShift, 1),
+            (_, Some('<'), '=') => (TokenKind::LessEqual, 1),
+            (Some('>'), Some('>'), '=') => (TokenKind::RightShiftEqual, 2),
+            (_, Some('>'), '>') => (TokenKind::RightShift, 1),
+            (_, Some('>'), '=') => (TokenKind::GreaterEqual, 1),
+            (_, Some('='), '=') => (TokenKind::EqEqual, 1),
+            (_, Some('!'), '=') => (TokenKind::NotEqual, 1),
+            (_, Some(':'), '=') => (TokenKind::ColonEqual, 1),
+            (_, Some('-'), '>') => (TokenKind::Rarrow, 1),
+            (_, Some('-'), '=') => (TokenKind::MinusEqual, 1),
+            (_, Some('+'), '=') => (TokenKind::PlusEqual, 1),
+            (_, Some('%'), '=') => (TokenKind::PercentEqual, 1),
+            (_, Some('&'), '=') => (TokenKind::AmperEqual, 1),
+            (_, Some('|'), '=') => (TokenKind::VbarEqual, 1),
+            (_, Some('^'), '=') => (TokenKind::CircumflexEqual, 1),
+            (_, Some('@'), '=') => (TokenKind::AtEqual, 1),
+            _ => (TokenKind::from_non_trivia_char(last), 0),
+        }
+    }
+
     const fn is_trivia(self) -> bool {
         matches!(
             self,
@@ -244,16 +717,26 @@ impl TokenKind {
 
 /// Simple zero allocation tokenizer for tokenizing trivia (and some tokens).
 ///
-/// The tokenizer must start at an offset that is trivia (e.g. not inside of a multiline string).
+/// The tokenizer must start at an offset that is the start of a token (e.g. not inside of a
+/// multiline string); string literals are lexed as a single [`TokenKind::String`] token, so
+/// [`first_non_trivia_token`] can walk past them instead of tripping over their quotes.
 ///
-/// The tokenizer doesn't guarantee any correctness after it returned a [`TokenKind::Other`]. That's why it
-/// will return [`TokenKind::Bogus`] for every character after until it reaches the end of the file.
+/// Each non-trivia lexeme is tokenized independently of the ones that came before it, following
+/// the design of `rustc_lexer`: every call to [`next_token`](SimpleTokenizer::next_token) or
+/// [`next_token_back`](SimpleTokenizer::next_token_back) looks only at the characters under the
+/// cursor and produces a self-describing [`TokenKind`], so the tokenizer never enters a global
+/// error state that would poison the rest of the stream.
 pub struct SimpleTokenizer<'a> {
     offset: TextSize,
     back_offset: TextSize,
     /// `true` when it is known that the current `back` line has no comment for sure.
     back_line_has_no_comment: bool,
-    bogus: bool,
+    /// `true` when [`Token::start_position`]/[`Token::end_position`] should be populated.
+    track_positions: bool,
+    /// The position at `offset`. Only meaningful when `track_positions` is `true`.
+    position: Position,
+    /// The position at `back_offset`. Only meaningful when `track_positions` is `true`.
+    back_position: Position,
     source: &'a str,
     cursor: Cursor<'a>,
 }
@@ -264,7 +747,17 @@ impl<'a> SimpleTokenizer<'a> {
             offset: range.start(),
             back_offset: range.end(),
             back_line_has_no_comment: false,
-            bogus: false,
+            track_positions: false,
+            position: Position {
+                offset: range.start(),
+                line: 0,
+                column: 0,
+            },
+            back_position: Position {
+                offset: range.end(),
+                line: 0,
+                column: 0,
+            },
             source,
             cursor: Cursor::new(&source[range]),
         }
@@ -289,41 +782,355 @@ impl<'a> SimpleTokenizer<'a> {
         tokenizer
     }
 
+    /// Enables line/column tracking: every yielded [`Token`] will carry a
+    /// [`Token::start_position`] and [`Token::end_position`]. Computing the initial positions costs
+    /// an upfront scan of the source up to the start and up to the end of this tokenizer's range;
+    /// every token after that is O(its own length), same as plain tokenization, except that a token
+    /// lexed backwards that contains a newline also rescans back to the start of its first line.
+    pub fn with_positions(mut self) -> Self {
+        self.position = position_at(self.source, self.offset);
+        self.back_position = position_at(self.source, self.back_offset);
+        self.track_positions = true;
+        self
+    }
+
+    /// Incrementally re-lexes `old_tokens` (the result of fully tokenizing some `old_source`)
+    /// after `edit`, a range in `old_source`, was replaced by `new_len` bytes of text to produce
+    /// `new_source`. Rather than re-tokenizing the whole file, this re-lexes only the span
+    /// covering the tokens that overlap `edit`, and reuses every other token unchanged (other than
+    /// shifting trailing tokens' offsets by `new_len - edit.len()`), the way rust-analyzer's
+    /// reparsing layer avoids redoing work outside a localized edit.
+    ///
+    /// Returns `None` if the edit can't be safely localized this way, in which case the caller
+    /// should fall back to fully re-tokenizing `new_source`. This is deliberately conservative:
+    /// it bails whenever a boundary token could span multiple lines (a string or a line
+    /// continuation, where a small edit can change how far the token reaches), when the untouched
+    /// token right before the edit doesn't re-lex to the same token, or when re-lexing the
+    /// affected window doesn't land back on the same boundary the untouched suffix expects.
+    pub fn reparse(
+        old_tokens: &[Token],
+        edit: TextRange,
+        new_len: TextSize,
+        new_source: &str,
+    ) -> Option<Vec<Token>> {
+        let start_idx = old_tokens.iter().position(|token| token.end() > edit.start())?;
+        let end_idx = old_tokens
+            .iter()
+            .rposition(|token| token.start() < edit.end())
+            .filter(|&index| index >= start_idx)?;
+
+        let boundary_start = &old_tokens[start_idx];
+        let boundary_end = &old_tokens[end_idx];
+
+        // A small edit next to a string or continuation can change how far it extends, so there's
+        // no "untouched" boundary to anchor on; a full re-lex is cheaper than getting this right.
+        if matches!(
+            boundary_start.kind(),
+            TokenKind::String | TokenKind::Continuation
+        ) || matches!(
+            boundary_end.kind(),
+            TokenKind::String | TokenKind::Continuation
+        ) {
+            return None;
+        }
+
+        let delta = i64::from(u32::from(new_len)) - i64::from(u32::from(edit.len()));
+
+        let window_start = boundary_start.start();
+        let new_window_end = shift_offset(boundary_end.end(), delta);
+
+        if new_window_end > new_source.text_len() {
+            return None;
+        }
+
+        // Re-lex from the token right before the edit (if any), since the edit can extend it
+        // (e.g. `a+ b` becoming `ax b`), and with no upper bound: bounding the tokenizer at
+        // `new_window_end` would make it impossible for a token to ever spill past that point,
+        // so an edit that merges two tokens into one spanning past the old boundary would go
+        // unnoticed. Instead, collect tokens one at a time and require that some token's end
+        // lands exactly on `new_window_end`; if one overshoots it first, the re-lexed window
+        // doesn't align with the rest of the old tokens and the incremental result can't be
+        // trusted.
+        let lead_idx = start_idx.saturating_sub(1);
+        let lead = &old_tokens[lead_idx];
+        let mut relexed = Vec::new();
+        let mut aligned = false;
+        for token in SimpleTokenizer::new(new_source, TextRange::new(lead.start(), new_source.text_len()))
+        {
+            let end = token.end();
+            relexed.push(token);
+            if end == new_window_end {
+                aligned = true;
+                break;
+            }
+            if end > new_window_end {
+                break;
+            }
+        }
+
+        if !aligned {
+            return None;
+        }
+
+        // The untouched token before the edit must re-lex exactly as before.
+        if lead_idx < start_idx
+            && (relexed[0].kind() != lead.kind() || relexed[0].range() != lead.range())
+        {
+            return None;
+        }
+
+        // `shift_token` only shifts a token's offset, not its line/column, so it's only valid
+        // when the edit didn't change how many newlines separate the boundary from the tokens
+        // after it. Bail out rather than hand back tokens with stale positions.
+        if let (Some(start_position), Some(end_position)) =
+            (boundary_start.start_position(), boundary_end.end_position())
+        {
+            let old_newlines = end_position.line - start_position.line;
+            let new_newlines = count_newlines(&new_source[TextRange::new(window_start, new_window_end)]);
+            if old_newlines != new_newlines {
+                return None;
+            }
+        }
+
+        let mut tokens = Vec::with_capacity(old_tokens.len() + relexed.len());
+        tokens.extend_from_slice(&old_tokens[..start_idx]);
+        tokens.extend(relexed.into_iter().skip(start_idx - lead_idx));
+        tokens.extend(
+            old_tokens[end_idx + 1..]
+                .iter()
+                .map(|token| shift_token(token, delta)),
+        );
+
+        Some(tokens)
+    }
+
     fn to_keyword_or_other(&self, range: TextRange) -> TokenKind {
         let source = &self.source[range];
         match source {
+            "False" => TokenKind::False,
+            "None" => TokenKind::None,
+            "True" => TokenKind::True,
+            "and" => TokenKind::And,
             "as" => TokenKind::As,
+            "assert" => TokenKind::Assert,
             "async" => TokenKind::Async,
+            "await" => TokenKind::Await,
+            "break" => TokenKind::Break,
+            "class" => TokenKind::Class,
+            "continue" => TokenKind::Continue,
+            "def" => TokenKind::Def,
+            "del" => TokenKind::Del,
+            "elif" => TokenKind::Elif,
             "else" => TokenKind::Else,
+            "except" => TokenKind::Except,
+            "finally" => TokenKind::Finally,
+            "for" => TokenKind::For,
+            "from" => TokenKind::From,
+            "global" => TokenKind::Global,
             "if" => TokenKind::If,
+            "import" => TokenKind::Import,
             "in" => TokenKind::In,
-            "match" => TokenKind::Match, // Match is a soft keyword that depends on the context but we can always lex it as a keyword and leave it to the caller (parser) to decide if it should be handled as an identifier or keyword.
+            "is" => TokenKind::Is,
+            "lambda" => TokenKind::Lambda,
+            "nonlocal" => TokenKind::Nonlocal,
+            "not" => TokenKind::Not,
+            "or" => TokenKind::Or,
+            "pass" => TokenKind::Pass,
+            "raise" => TokenKind::Raise,
+            "return" => TokenKind::Return,
+            "try" => TokenKind::Try,
+            "while" => TokenKind::While,
             "with" => TokenKind::With,
-            // ...,
-            _ => TokenKind::Other, // Potentially an identifier, but only if it isn't a string prefix. We can ignore this for now https://docs.python.org/3/reference/lexical_analysis.html#string-and-bytes-literals
+            "yield" => TokenKind::Yield,
+            // Soft keywords are valid identifiers almost everywhere and only keywords in specific
+            // syntactic contexts, but we always lex them as keywords and leave it to the caller
+            // (parser) to decide if it should be handled as an identifier instead.
+            "match" => TokenKind::Match,
+            "case" => TokenKind::Case,
+            "type" => TokenKind::Type,
+            _ => TokenKind::Other, // Potentially an identifier. String prefixes are handled separately by `match_string_start`.
+        }
+    }
+
+    /// Eats a string literal (including its optional prefix and quotes) starting at the current
+    /// cursor position, if there is one. Returns `true` and leaves the cursor positioned after the
+    /// closing quote (or at the end of the source, for an unterminated string) if a string was
+    /// eaten; otherwise leaves the cursor untouched and returns `false`.
+    fn eat_string_forward(&mut self) -> bool {
+        let Some((prefix_len, quote_len)) = match_string_start(self.cursor.chars().as_str())
+        else {
+            return false;
+        };
+
+        // `r`, `R` make the string raw: backslashes don't escape the closing quote.
+        let is_raw = self.cursor.chars().as_str()[..prefix_len]
+            .chars()
+            .any(|c| c.eq_ignore_ascii_case(&'r'));
+
+        let quote_char = self.cursor.chars().as_str().as_bytes()[prefix_len] as char;
+
+        for _ in 0..prefix_len + quote_len {
+            self.cursor.bump();
+        }
+
+        loop {
+            let Some(c) = self.cursor.bump() else {
+                // Unterminated string; consume up to the end of the source.
+                break;
+            };
+
+            if !is_raw && c == '\\' {
+                self.cursor.bump();
+            } else if c == quote_char {
+                if quote_len == 1 {
+                    break;
+                }
+
+                let rest = self.cursor.chars().as_str();
+                if rest.as_bytes().first() == Some(&(quote_char as u8))
+                    && rest.as_bytes().get(1) == Some(&(quote_char as u8))
+                {
+                    self.cursor.bump();
+                    self.cursor.bump();
+                    break;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Scans backwards from an already-consumed closing `quote_char`, looking for the matching
+    /// opening `quote_char` (repeated `quote_len` times). If `skip_escaped` is `true`, an opening
+    /// candidate preceded by an odd number of backslashes is treated as escaped string content and
+    /// skipped over, mirroring non-raw forward lexing. Returns `false` (having consumed up to the
+    /// start of the source) if no opening delimiter is found, i.e. the string is unterminated.
+    fn scan_string_open_back(&mut self, quote_char: char, quote_len: usize, skip_escaped: bool) -> bool {
+        loop {
+            let Some(c) = self.cursor.bump_back() else {
+                return false;
+            };
+
+            if c != quote_char {
+                continue;
+            }
+
+            if skip_escaped {
+                let remaining = self.cursor.chars().as_str();
+                let escaped = remaining.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1;
+                if escaped {
+                    continue;
+                }
+            }
+
+            if quote_len == 1 {
+                return true;
+            }
+
+            let remaining = self.cursor.chars().as_str();
+            let bytes = remaining.as_bytes();
+            if bytes.len() >= 2
+                && bytes[bytes.len() - 1] == quote_char as u8
+                && bytes[bytes.len() - 2] == quote_char as u8
+            {
+                self.cursor.bump_back();
+                self.cursor.bump_back();
+                return true;
+            }
+            // A lone quote inside a triple-quoted string is just content; keep scanning.
+        }
+    }
+
+    /// Scans backwards from an already-consumed closing `quote_char`, over the string's contents,
+    /// its opening quotes, and its prefix (if any). Leaves the cursor positioned right before the
+    /// string's first character (or at the start of the source, for an unterminated string).
+    fn eat_string_back(&mut self, quote_char: char) {
+        let quote_len = {
+            let remaining = self.cursor.chars().as_str();
+            let bytes = remaining.as_bytes();
+            if bytes.len() >= 2
+                && bytes[bytes.len() - 1] == quote_char as u8
+                && bytes[bytes.len() - 2] == quote_char as u8
+            {
+                self.cursor.bump_back();
+                self.cursor.bump_back();
+                3
+            } else {
+                1
+            }
+        };
+
+        let savepoint = self.cursor.clone();
+
+        // Try the raw interpretation first: a raw string's opening delimiter is the nearest bare
+        // quote, regardless of any preceding backslashes.
+        if self.scan_string_open_back(quote_char, quote_len, false) {
+            let remaining = self.cursor.chars().as_str();
+            let prefix_len = match_string_prefix_end(remaining);
+            if remaining[remaining.len() - prefix_len..]
+                .chars()
+                .any(|c| c.eq_ignore_ascii_case(&'r'))
+            {
+                for _ in 0..prefix_len {
+                    self.cursor.bump_back();
+                }
+                return;
+            }
+        }
+
+        // Not a raw string (or no bare quote at all): redo, treating `\`-escaped quotes as
+        // part of the string's contents rather than its closing delimiter.
+        self.cursor = savepoint;
+        if self.scan_string_open_back(quote_char, quote_len, true) {
+            let prefix_len = match_string_prefix_end(self.cursor.chars().as_str());
+            for _ in 0..prefix_len {
+                self.cursor.bump_back();
+            }
+        }
+    }
+
+    /// Builds the [`Token`] for the lexeme the cursor just consumed going forward, populating its
+    /// positions (and advancing [`Self::position`]) if position tracking is enabled.
+    fn make_token(&mut self, kind: TokenKind) -> Token {
+        let token_len = self.cursor.token_len();
+        let range = TextRange::at(self.offset, token_len);
+
+        let (start_position, end_position) = if self.track_positions {
+            let start = self.position;
+            let end = advance_position(start, &self.source[range]);
+            self.position = end;
+            (Some(start), Some(end))
+        } else {
+            (None, None)
+        };
+
+        self.offset += token_len;
+
+        Token {
+            kind,
+            range,
+            start_position,
+            end_position,
         }
     }
 
     fn next_token(&mut self) -> Token {
         self.cursor.start_token();
 
+        if self.eat_string_forward() {
+            return self.make_token(TokenKind::String);
+        }
+
         let Some(first) = self.cursor.bump() else {
+            let position = self.track_positions.then_some(self.position);
             return Token {
                 kind: TokenKind::EndOfFile,
                 range: TextRange::empty(self.offset),
+                start_position: position,
+                end_position: position,
             };
         };
 
-        if self.bogus {
-            let token = Token {
-                kind: TokenKind::Bogus,
-                range: TextRange::at(self.offset, first.text_len()),
-            };
-
-            self.offset += first.text_len();
-            return token;
-        }
-
         let kind = match first {
             ' ' | '\t' => {
                 self.cursor.eat_while(|c| matches!(c, ' ' | '\t'));
@@ -345,33 +1152,80 @@ impl<'a> SimpleTokenizer<'a> {
             '\\' => TokenKind::Continuation,
 
             c => {
-                let kind = if is_identifier_start(c) {
+                if is_identifier_start(c) {
                     self.cursor.eat_while(is_identifier_continuation);
                     let token_len = self.cursor.token_len();
 
                     let range = TextRange::at(self.offset, token_len);
                     self.to_keyword_or_other(range)
+                } else if is_identifier_continuation(c) {
+                    // An identifier-continuation run without an identifier-start character,
+                    // e.g. `555`.
+                    self.cursor.eat_while(is_identifier_continuation);
+                    TokenKind::Number
                 } else {
-                    TokenKind::from_non_trivia_char(c)
-                };
-
-                if kind == TokenKind::Other {
-                    self.bogus = true;
+                    // Either a (possibly multi-character) operator, or a genuinely unknown
+                    // character; either way, `match_operator` consumes exactly as many characters
+                    // as belong to the token, so unlike rustc_lexer's `Unknown` we never latch
+                    // into a global error state.
+                    let mut rest = self.cursor.chars();
+                    let second = rest.next();
+                    let third = rest.next();
+
+                    let (kind, extra) = TokenKind::match_operator(c, second, third);
+                    for _ in 0..extra {
+                        self.cursor.bump();
+                    }
+                    kind
                 }
-                kind
             }
         };
 
+        self.make_token(kind)
+    }
+
+    /// Builds the [`Token`] for the lexeme the cursor just consumed going backwards, populating
+    /// its positions (and rewinding [`Self::back_position`]) if position tracking is enabled.
+    fn make_token_back(&mut self, kind: TokenKind) -> Token {
         let token_len = self.cursor.token_len();
+        let start = self.back_offset - token_len;
+        let range = TextRange::at(start, token_len);
+
+        let (start_position, end_position) = if self.track_positions {
+            let end = self.back_position;
+            let token_text = &self.source[range];
+            let newlines = count_newlines(token_text);
+
+            // When the token doesn't contain a newline, `start` is on the same line as `end`,
+            // so its column is just `end`'s column minus the token's own length: O(token
+            // length), not a rescan of everything back to the start of the line. Only fall
+            // back to the expensive line-start search on the rare token that actually crosses a
+            // newline (at most once per newline in the source, over a full backward traversal).
+            let column = if newlines == 0 {
+                end.column - token_text.chars().count() as u32
+            } else {
+                column_before(self.source, start)
+            };
 
-        let token = Token {
-            kind,
-            range: TextRange::at(self.offset, token_len),
+            let start_position = Position {
+                offset: start,
+                line: end.line - newlines,
+                column,
+            };
+            self.back_position = start_position;
+            (Some(start_position), Some(end))
+        } else {
+            (None, None)
         };
 
-        self.offset += token_len;
+        self.back_offset = start;
 
-        token
+        Token {
+            kind,
+            range,
+            start_position,
+            end_position,
+        }
     }
 
     /// Returns the next token from the back. Prefer iterating forwards. Iterating backwards is significantly more expensive
@@ -380,22 +1234,15 @@ impl<'a> SimpleTokenizer<'a> {
         self.cursor.start_token();
 
         let Some(last) = self.cursor.bump_back() else {
+            let position = self.track_positions.then_some(self.back_position);
             return Token {
                 kind: TokenKind::EndOfFile,
                 range: TextRange::empty(self.back_offset),
+                start_position: position,
+                end_position: position,
             };
         };
 
-        if self.bogus {
-            let token = Token {
-                kind: TokenKind::Bogus,
-                range: TextRange::at(self.back_offset - last.text_len(), last.text_len()),
-            };
-
-            self.back_offset -= last.text_len();
-            return token;
-        }
-
         let kind = match last {
             // This may not be 100% correct because it will lex-out trailing whitespace from a comment
             // as whitespace rather than being part of the token. This shouldn't matter for what we use the lexer for.
@@ -418,6 +1265,13 @@ impl<'a> SimpleTokenizer<'a> {
             // Empty comment (could also be a comment nested in another comment, but this shouldn't matter for what we use the lexer for)
             '#' => TokenKind::Comment,
 
+            // A string's closing quote. Scan backwards over its contents to find the opening
+            // quote (and prefix), rather than running it through the comment-detection logic below.
+            c @ ('\'' | '"') => {
+                self.eat_string_back(c);
+                TokenKind::String
+            }
+
             // For all other tokens, test if the character isn't part of a comment.
             c => {
                 // Skip the test whether there's a preceding comment if it has been performed before.
@@ -465,52 +1319,41 @@ impl<'a> SimpleTokenizer<'a> {
                     TokenKind::Comment
                 } else if c == '\\' {
                     TokenKind::Continuation
-                } else {
-                    let kind = if is_identifier_continuation(c) {
-                        // if we only have identifier continuations but no start (e.g. 555) we
-                        // don't want to consume the chars, so in that case, we want to rewind the
-                        // cursor to here
-                        let savepoint = self.cursor.clone();
-                        self.cursor.eat_back_while(is_identifier_continuation);
-
-                        let token_len = self.cursor.token_len();
-                        let range = TextRange::at(self.back_offset - token_len, token_len);
-
-                        if self.source[range]
-                            .chars()
-                            .next()
-                            .is_some_and(is_identifier_start)
-                        {
-                            self.to_keyword_or_other(range)
-                        } else {
-                            self.cursor = savepoint;
-                            TokenKind::Other
-                        }
-                    } else {
-                        TokenKind::from_non_trivia_char(c)
-                    };
+                } else if is_identifier_continuation(c) {
+                    self.cursor.eat_back_while(is_identifier_continuation);
 
-                    if kind == TokenKind::Other {
-                        self.bogus = true;
+                    let token_len = self.cursor.token_len();
+                    let range = TextRange::at(self.back_offset - token_len, token_len);
+
+                    if self.source[range]
+                        .chars()
+                        .next()
+                        .is_some_and(is_identifier_start)
+                    {
+                        self.to_keyword_or_other(range)
+                    } else {
+                        // An identifier-continuation run without an identifier-start character,
+                        // e.g. `555`.
+                        TokenKind::Number
+                    }
+                } else {
+                    // A (possibly multi-character) operator. Mirrors the forward path, peeking the
+                    // one or two characters before `c` instead of after it.
+                    let before = self.cursor.chars().as_str();
+                    let mut preceding = before.chars().rev();
+                    let prev = preceding.next();
+                    let prev2 = preceding.next();
+
+                    let (kind, extra) = TokenKind::match_operator_back(c, prev, prev2);
+                    for _ in 0..extra {
+                        self.cursor.bump_back();
                     }
-
                     kind
                 }
             }
         };
 
-        let token_len = self.cursor.token_len();
-
-        let start = self.back_offset - token_len;
-
-        let token = Token {
-            kind,
-            range: TextRange::at(start, token_len),
-        };
-
-        self.back_offset = start;
-
-        token
+        self.make_token_back(kind)
     }
 
     pub fn skip_trivia(self) -> impl Iterator<Item = Token> + DoubleEndedIterator + 'a {
@@ -656,8 +1499,7 @@ mod tests {
 
         let test_case = tokenize(source);
         assert_debug_snapshot!(test_case.tokens());
-
-        // note: not reversible: [other, bogus, bogus] vs [bogus, bogus, other]
+        test_case.assert_reverse_tokenization();
     }
 
     #[test]
@@ -670,6 +1512,95 @@ mod tests {
         test_case.assert_reverse_tokenization();
     }
 
+    #[test]
+    fn tokenize_keywords() {
+        let source =
+            "False None True and as assert async await break class continue def del elif else \
+             except finally for from global if import in is lambda nonlocal not or pass raise \
+             return try while with yield match case type";
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_operators() {
+        let source = "== != <= >= -> := ** // << >> + - * / % & | ^ ~ @ ; \
+                       += -= *= /= //= %= &= |= ^= >>= <<= **= @=";
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_string() {
+        let source = r#"'a string' "another" b"bytes" rb'raw bytes' f'f-string'"#;
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_string_with_escaped_quote() {
+        let source = r#"'it\'s a test'"#;
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_raw_string() {
+        let source = r#"r'raw\nstring'"#;
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_triple_quoted_string() {
+        let source = "x = \"\"\"a\nmultiline\nstring\"\"\"\ny = 1";
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_unterminated_string() {
+        let source = r#"x = "abc"#;
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+
+        // Not reversible: there's no closing quote for `next_token_back` to anchor on, so it
+        // lexes the trailing `abc` as an identifier rather than recovering the `String` token.
+    }
+
+    #[test]
+    fn tokenize_string_after_multi_byte_identifier() {
+        // `é` ends the preceding identifier one byte before the opening quote, with no ASCII
+        // separator in between; backward tokenization of the string must not panic trying to
+        // slice mid-codepoint while looking for a string prefix.
+        let source = "café'x'";
+
+        let test_case = tokenize(source);
+
+        assert_debug_snapshot!(test_case.tokens());
+        test_case.assert_reverse_tokenization();
+    }
+
     #[test]
     fn tokenize_substring() {
         let source = "('some string') # comment";
@@ -702,7 +1633,100 @@ mod tests {
         let test_case = tokenize(source);
 
         assert_debug_snapshot!(test_case.tokens());
-        assert_debug_snapshot!("Reverse", test_case.tokenize_reverse());
+        test_case.assert_reverse_tokenization();
+    }
+
+    #[test]
+    fn tokenize_with_positions() {
+        let source = "x = 1\n    y = 2";
+
+        let tokens: Vec<_> = SimpleTokenizer::new(source, TextRange::up_to(source.text_len()))
+            .with_positions()
+            .collect();
+
+        assert_debug_snapshot!(tokens);
+    }
+
+    #[test]
+    fn tokenize_with_positions_reverse_agrees() {
+        let source = "x = 1\n    y = 2";
+        let range = TextRange::up_to(source.text_len());
+
+        let forward: Vec<_> = SimpleTokenizer::new(source, range)
+            .with_positions()
+            .collect();
+
+        let mut backward: Vec<_> = SimpleTokenizer::new(source, range)
+            .with_positions()
+            .rev()
+            .collect();
+        backward.reverse();
+
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn reparse_identifier_rename() {
+        let old_source = "foo = bar + 1";
+        let old_tokens: Vec<_> = tokenize(old_source).tokens().to_vec();
+
+        // Rename `bar` (offsets 6..9) to `barbaz`.
+        let new_source = "foo = barbaz + 1";
+        let edit = TextRange::new(TextSize::new(6), TextSize::new(9));
+
+        let reparsed =
+            SimpleTokenizer::reparse(&old_tokens, edit, TextSize::new(6), new_source).unwrap();
+
+        assert_eq!(&reparsed, tokenize(new_source).tokens());
+    }
+
+    #[test]
+    fn reparse_bails_on_boundary_string() {
+        let old_source = "x = 'abc' + y";
+        let old_tokens: Vec<_> = tokenize(old_source).tokens().to_vec();
+
+        // Edit inside the string literal (offsets 5..8, the `abc`).
+        let new_source = "x = 'abcd' + y";
+        let edit = TextRange::new(TextSize::new(5), TextSize::new(8));
+
+        assert_eq!(
+            SimpleTokenizer::reparse(&old_tokens, edit, TextSize::new(4), new_source),
+            None
+        );
+    }
+
+    #[test]
+    fn reparse_bails_when_edit_merges_tokens() {
+        let old_source = "1 2";
+        let old_tokens: Vec<_> = tokenize(old_source).tokens().to_vec();
+
+        // Replace the space (offsets 1..2) with `x`, merging `1` and `2` into a single token
+        // that spills past the old boundary.
+        let new_source = "1x2";
+        let edit = TextRange::new(TextSize::new(1), TextSize::new(2));
+
+        assert_eq!(
+            SimpleTokenizer::reparse(&old_tokens, edit, TextSize::new(1), new_source),
+            None
+        );
+    }
+
+    #[test]
+    fn reparse_bails_on_newline_count_mismatch() {
+        let old_source = "x = 1;y = 2";
+        let old_tokens: Vec<_> = SimpleTokenizer::new(old_source, TextRange::up_to(old_source.text_len()))
+            .with_positions()
+            .collect();
+
+        // Replace the `;` (offsets 5..6) with a newline: same length, so `delta` is 0, but the
+        // number of newlines between the boundary tokens has changed.
+        let new_source = "x = 1\ny = 2";
+        let edit = TextRange::new(TextSize::new(5), TextSize::new(6));
+
+        assert_eq!(
+            SimpleTokenizer::reparse(&old_tokens, edit, TextSize::new(1), new_source),
+            None
+        );
     }
 
     #[test]